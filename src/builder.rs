@@ -0,0 +1,396 @@
+//! Fluent builders for programmatically generating valid CSAF documents.
+//!
+//! `CsafBuilder` fills in the boilerplate every document needs - an initial `revision_history`
+//! entry, `tracking.current_release_date` kept in sync with the latest revision, deduplicated
+//! `product_id`s - and [`CsafBuilder::build`] runs [`crate::validation`]'s mandatory profile
+//! before handing back the finished [`Csaf`].
+
+use std::collections::HashSet;
+
+use crate::definitions::ProductIdT;
+use crate::document::{Document, Publisher, Revision, Tracking, TrackingStatus};
+use crate::product_tree::{Branch, BranchCategory, FullProductName, ProductIdentificationHelper, ProductTree};
+use crate::validation::{Profile, ValidationError};
+use crate::vulnerability::{ProductStatus, Remediation, RemediationCategory, Vulnerability};
+use crate::Csaf;
+
+/// Builds the `document` property: publisher, category, title and an append-only revision
+/// history that drives `tracking.current_release_date`/`tracking.version`.
+#[derive(Debug, Clone)]
+pub struct DocumentBuilder {
+    category: String,
+    title: String,
+    publisher: Publisher,
+    tracking_id: String,
+    revision_history: Vec<Revision>,
+}
+
+impl DocumentBuilder {
+    /// Creates a document with a single initial revision - `version`/`date` become both
+    /// `tracking.initial_release_date`/`current_release_date` and the first `revision_history`
+    /// entry.
+    pub fn new(
+        category: impl Into<String>,
+        title: impl Into<String>,
+        publisher: Publisher,
+        tracking_id: impl Into<String>,
+        initial_version: impl Into<String>,
+        initial_release_date: impl Into<String>,
+    ) -> Self {
+        Self {
+            category: category.into(),
+            title: title.into(),
+            publisher,
+            tracking_id: tracking_id.into(),
+            revision_history: vec![Revision {
+                date: initial_release_date.into(),
+                legacy_version: None,
+                number: initial_version.into(),
+                summary: "Initial version.".to_string(),
+            }],
+        }
+    }
+
+    /// Appends a new revision, which becomes the latest `tracking.current_release_date`/`version`.
+    pub fn revision(
+        mut self,
+        version: impl Into<String>,
+        date: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Self {
+        self.revision_history.push(Revision {
+            date: date.into(),
+            legacy_version: None,
+            number: version.into(),
+            summary: summary.into(),
+        });
+        self
+    }
+
+    fn build(self) -> Document {
+        // `DocumentBuilder::new` always seeds one revision, so these are never empty.
+        #[allow(clippy::unwrap_used)]
+        let first = self.revision_history.first().unwrap().clone();
+        #[allow(clippy::unwrap_used)]
+        let latest = self.revision_history.last().unwrap().clone();
+
+        Document {
+            acknowledgments: None,
+            aggregate_severity: None,
+            category: self.category,
+            csaf_version: crate::document::CsafVersion::V2_0,
+            distribution: None,
+            lang: None,
+            notes: None,
+            publisher: self.publisher,
+            references: None,
+            source_lang: None,
+            title: self.title,
+            tracking: Tracking {
+                aliases: None,
+                current_release_date: latest.date,
+                generator: None,
+                id: self.tracking_id,
+                initial_release_date: first.date,
+                revision_history: self.revision_history,
+                status: TrackingStatus::Final,
+                version: latest.number,
+            },
+        }
+    }
+}
+
+/// Builds the `product_tree` property, nesting `vendor -> product_name -> product_version`
+/// branches and auto-generating a unique, deduplicated `product_id` per `(vendor, product_name,
+/// version)` triple.
+#[derive(Debug, Clone, Default)]
+pub struct ProductTreeBuilder {
+    vendors: Vec<Branch>,
+    product_ids: HashSet<ProductIdT>,
+}
+
+impl ProductTreeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `vendor/product_name/version`, returning its `product_id`. Calling this again with
+    /// the same triple returns the same `product_id` rather than creating a duplicate branch.
+    pub fn add_product(
+        &mut self,
+        vendor: impl Into<String>,
+        product_name: impl Into<String>,
+        version: impl Into<String>,
+        helper: Option<ProductIdentificationHelper>,
+    ) -> ProductIdT {
+        let vendor = vendor.into();
+        let product_name = product_name.into();
+        let version = version.into();
+
+        let vendor_branch = Self::find_or_insert(&mut self.vendors, BranchCategory::Vendor, &vendor);
+        let product_branch = Self::find_or_insert(
+            vendor_branch.branches.get_or_insert_with(Vec::new),
+            BranchCategory::ProductName,
+            &product_name,
+        );
+        let version_branches = product_branch.branches.get_or_insert_with(Vec::new);
+
+        // Same (vendor, product_name, version) triple already has a branch - return its
+        // product_id rather than generating a second one for the same leaf.
+        if let Some(existing) = version_branches
+            .iter()
+            .find(|branch| branch.category == BranchCategory::ProductVersion && branch.name == version)
+        {
+            if let Some(product) = &existing.product {
+                return product.product_id.clone();
+            }
+        }
+
+        let product_id = self.unique_product_id(&vendor, &product_name, &version);
+        version_branches.push(Branch {
+            branches: None,
+            category: BranchCategory::ProductVersion,
+            name: version.clone(),
+            product: Some(FullProductName {
+                name: format!("{vendor} {product_name} {version}"),
+                product_id: product_id.clone(),
+                product_identification_helper: helper,
+            }),
+        });
+
+        product_id
+    }
+
+    fn find_or_insert<'a>(
+        branches: &'a mut Vec<Branch>,
+        category: BranchCategory,
+        name: &str,
+    ) -> &'a mut Branch {
+        if let Some(index) = branches.iter().position(|b| b.category == category && b.name == name) {
+            return &mut branches[index];
+        }
+        branches.push(Branch {
+            branches: None,
+            category,
+            name: name.to_string(),
+            product: None,
+        });
+        // Just pushed, so this is always present.
+        #[allow(clippy::unwrap_used)]
+        branches.last_mut().unwrap()
+    }
+
+    fn unique_product_id(&mut self, vendor: &str, product_name: &str, version: &str) -> ProductIdT {
+        let slug = |s: &str| {
+            s.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                .collect::<String>()
+        };
+        let base = format!("{}:{}:{}", slug(vendor), slug(product_name), slug(version));
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+        while self.product_ids.contains(&candidate) {
+            suffix += 1;
+            candidate = format!("{base}-{suffix}");
+        }
+        self.product_ids.insert(candidate.clone());
+        candidate
+    }
+
+    fn build(self) -> ProductTree {
+        ProductTree {
+            branches: Some(self.vendors),
+            full_product_names: None,
+            product_groups: None,
+            relationships: None,
+        }
+    }
+}
+
+/// Builds a single `vulnerabilities[]` entry.
+#[derive(Debug, Clone, Default)]
+pub struct VulnerabilityBuilder {
+    cve: Option<String>,
+    title: Option<String>,
+    product_status: ProductStatus,
+    remediations: Vec<Remediation>,
+}
+
+impl VulnerabilityBuilder {
+    pub fn new(cve: impl Into<Option<String>>) -> Self {
+        Self {
+            cve: cve.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Marks `product_id` as `known_affected`.
+    pub fn affects(&mut self, product_id: ProductIdT) -> &mut Self {
+        self.product_status.known_affected.get_or_insert_with(Vec::new).push(product_id);
+        self
+    }
+
+    /// Marks `product_id` as `known_not_affected`.
+    pub fn not_affected(&mut self, product_id: ProductIdT) -> &mut Self {
+        self.product_status.known_not_affected.get_or_insert_with(Vec::new).push(product_id);
+        self
+    }
+
+    /// Marks `product_id` as `fixed`.
+    pub fn fixed(&mut self, product_id: ProductIdT) -> &mut Self {
+        self.product_status.fixed.get_or_insert_with(Vec::new).push(product_id);
+        self
+    }
+
+    pub fn remediation(
+        &mut self,
+        category: RemediationCategory,
+        details: impl Into<String>,
+        product_ids: Vec<ProductIdT>,
+    ) -> &mut Self {
+        self.remediations.push(Remediation {
+            category,
+            date: None,
+            details: details.into(),
+            entitlements: None,
+            group_ids: None,
+            product_ids: Some(product_ids),
+            restart_required: None,
+            url: None,
+        });
+        self
+    }
+
+    fn build(self) -> Vulnerability {
+        Vulnerability {
+            cve: self.cve,
+            title: self.title,
+            product_status: Some(self.product_status),
+            remediations: (!self.remediations.is_empty()).then_some(self.remediations),
+            ..Default::default()
+        }
+    }
+}
+
+/// Top-level builder tying `document`, `product_tree` and `vulnerabilities` together.
+#[derive(Debug, Clone)]
+pub struct CsafBuilder {
+    document: DocumentBuilder,
+    product_tree: ProductTreeBuilder,
+    vulnerabilities: Vec<VulnerabilityBuilder>,
+}
+
+impl CsafBuilder {
+    pub fn new(document: DocumentBuilder) -> Self {
+        Self {
+            document,
+            product_tree: ProductTreeBuilder::new(),
+            vulnerabilities: Vec::new(),
+        }
+    }
+
+    /// Appends a new document revision; see [`DocumentBuilder::revision`].
+    pub fn revision(
+        mut self,
+        version: impl Into<String>,
+        date: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Self {
+        self.document = self.document.revision(version, date, summary);
+        self
+    }
+
+    /// See [`ProductTreeBuilder::add_product`].
+    pub fn add_product(
+        &mut self,
+        vendor: impl Into<String>,
+        product_name: impl Into<String>,
+        version: impl Into<String>,
+        helper: Option<ProductIdentificationHelper>,
+    ) -> ProductIdT {
+        self.product_tree.add_product(vendor, product_name, version, helper)
+    }
+
+    /// Starts a new `vulnerabilities[]` entry and returns a handle to keep configuring it, e.g.
+    /// `builder.add_vulnerability(Some("CVE-2024-0001".into())).affects(product_id);`.
+    pub fn add_vulnerability(&mut self, cve: impl Into<Option<String>>) -> &mut VulnerabilityBuilder {
+        self.vulnerabilities.push(VulnerabilityBuilder::new(cve));
+        // Just pushed, so this is always present.
+        #[allow(clippy::unwrap_used)]
+        self.vulnerabilities.last_mut().unwrap()
+    }
+
+    /// Assembles the document and runs [`Profile::Mandatory`] validation before returning it.
+    pub fn build(self) -> Result<Csaf, Vec<ValidationError>> {
+        let csaf = Csaf {
+            document: self.document.build(),
+            product_tree: Some(self.product_tree.build()),
+            vulnerabilities: Some(
+                self.vulnerabilities
+                    .into_iter()
+                    .map(VulnerabilityBuilder::build)
+                    .collect(),
+            ),
+        };
+        csaf.validate(Profile::Mandatory)?;
+        Ok(csaf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::PublisherCategory;
+
+    fn publisher() -> Publisher {
+        Publisher {
+            category: PublisherCategory::Vendor,
+            contact_details: None,
+            issuing_authority: None,
+            name: "Test".to_string(),
+            namespace: "https://example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn add_product_is_idempotent_for_the_same_triple() {
+        let mut builder = ProductTreeBuilder::new();
+        let first = builder.add_product("Acme", "Widget", "1.0", None);
+        let second = builder.add_product("Acme", "Widget", "1.0", None);
+        assert_eq!(first, second);
+
+        let tree = builder.build();
+        let product_ids = tree.all_defined_product_ids();
+        assert_eq!(product_ids.iter().filter(|id| **id == first).count(), 1);
+    }
+
+    #[test]
+    fn add_product_gives_distinct_ids_for_distinct_versions() {
+        let mut builder = ProductTreeBuilder::new();
+        let v1 = builder.add_product("Acme", "Widget", "1.0", None);
+        let v2 = builder.add_product("Acme", "Widget", "2.0", None);
+        assert_ne!(v1, v2);
+    }
+
+    #[test]
+    fn csaf_builder_assembles_a_valid_document() {
+        let mut builder = CsafBuilder::new(DocumentBuilder::new(
+            "csaf_vex",
+            "Test advisory",
+            publisher(),
+            "TEST-1",
+            "1",
+            "2024-01-01T00:00:00Z",
+        ));
+        let product_id = builder.add_product("Acme", "Widget", "1.0", None);
+        builder.add_vulnerability(Some("CVE-2024-0001".to_string())).affects(product_id);
+
+        let csaf = builder.build().unwrap();
+        assert_eq!(csaf.document.tracking.version, "1");
+    }
+}
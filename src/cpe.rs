@@ -0,0 +1,202 @@
+//! Syntactic validation of the `cpe` field in
+//! [`product_identification_helper`](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.1/prose/csaf-v2.1-editor-draft.md#3221-product-tree-property---cpe)
+//! against the CPE 2.3 formatted-string and legacy CPE 2.2 URI grammars, matching the
+//! `cpe:2.3:...` / `cpe:/...` patterns the upstream JSON schema ships.
+
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+/// A syntactically well-formed CPE, either the 2.3 formatted-string form (`cpe:2.3:a:...`) or
+/// the legacy 2.2 URI form (`cpe:/a:...`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpe(String);
+
+impl Cpe {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cpe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CpeParseError {
+    #[error("`{0}` does not start with a recognized CPE prefix (`cpe:2.3:` or `cpe:/`)")]
+    UnrecognizedPrefix(String),
+    #[error("CPE 2.3 formatted string `{0}` must have exactly 11 colon-separated components, found {1}")]
+    WrongComponentCount(String, usize),
+    #[error("CPE URI `{0}` must have at most 6 colon-separated components, found {1}")]
+    TooManyUriComponents(String, usize),
+    #[error("component `{1}` of CPE `{0}` contains an illegal unescaped character")]
+    IllegalCharacter(String, String),
+    #[error("language component `{1}` of CPE `{0}` is not a valid language tag")]
+    InvalidLanguage(String, String),
+}
+
+/// The 11 well-formed-name attributes, in the order CPE 2.3 formatted strings encode them.
+const WFN_FIELDS: [&str; 11] = [
+    "part", "vendor", "product", "version", "update", "edition", "language", "sw_edition",
+    "target_sw", "target_hw", "other",
+];
+
+impl FromStr for Cpe {
+    type Err = CpeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_prefix("cpe:2.3:") {
+            validate_wfn(s, rest)?;
+        } else if s.starts_with("cpe:/") {
+            validate_uri(s)?;
+        } else {
+            return Err(CpeParseError::UnrecognizedPrefix(s.to_string()));
+        }
+        Ok(Cpe(s.to_string()))
+    }
+}
+
+/// A single WFN attribute-value component: `*`, `-`, or a string of unreserved characters
+/// (including the unescaped `.` and `-` found in real-world version strings, e.g. `8.0.6001`)
+/// with backslash-escaped special characters, optionally wrapped in leading/trailing unquoted `*`.
+fn component_regex() -> Regex {
+    #[allow(clippy::expect_used)]
+    Regex::new(r##"^(\*|-|\*?([a-zA-Z0-9_.\-]|\\[\\!"#$%&'()+,./:;<=>@\[\]^`\{\|\}~?*-])+\*?)$"##)
+        .expect("component regex is a fixed, valid pattern")
+}
+
+fn language_regex() -> Regex {
+    #[allow(clippy::expect_used)]
+    Regex::new(r"^(\*|-|[a-z]{2,3}(-([A-Za-z]{2}|[0-9]{3}))?)$")
+        .expect("language regex is a fixed, valid pattern")
+}
+
+/// Split `s` on `:` characters, except where the colon is backslash-escaped (`\:`) - CPE 2.3
+/// attribute values may contain an escaped colon, which `component_regex` itself allows, so a
+/// naive `str::split(':')` would wrongly cut a valid component in two.
+fn split_unescaped_colons(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            ':' => {
+                parts.push(&s[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn validate_wfn(full: &str, rest: &str) -> Result<(), CpeParseError> {
+    let parts = split_unescaped_colons(rest);
+    if parts.len() != WFN_FIELDS.len() {
+        return Err(CpeParseError::WrongComponentCount(full.to_string(), parts.len()));
+    }
+
+    let component = component_regex();
+    let language = language_regex();
+    for (field, value) in WFN_FIELDS.iter().zip(parts.iter()) {
+        if *field == "part" {
+            if !matches!(*value, "a" | "h" | "o" | "*" | "-") {
+                return Err(CpeParseError::IllegalCharacter(full.to_string(), (*value).to_string()));
+            }
+        } else if *field == "language" {
+            if !language.is_match(value) {
+                return Err(CpeParseError::InvalidLanguage(full.to_string(), (*value).to_string()));
+            }
+        } else if !component.is_match(value) {
+            return Err(CpeParseError::IllegalCharacter(full.to_string(), (*value).to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// The legacy `cpe:/[AHOaho]?:vendor:product:version:update:edition:language` URI form, capped
+/// at 6 components after the part.
+fn validate_uri(full: &str) -> Result<(), CpeParseError> {
+    let rest = full.trim_start_matches("cpe:/");
+    let parts: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        split_unescaped_colons(rest)
+    };
+    let components = parts.len().saturating_sub(1);
+    if components > 6 {
+        return Err(CpeParseError::TooManyUriComponents(full.to_string(), components));
+    }
+    if let Some(part) = parts.first() {
+        if !matches!(*part, "a" | "h" | "o" | "A" | "H" | "O" | "") {
+            return Err(CpeParseError::IllegalCharacter(full.to_string(), (*part).to_string()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_formed_name_parses() {
+        assert!(Cpe::from_str("cpe:2.3:a:microsoft:internet_explorer:8.0.6001:beta:*:*:*:*:*:*").is_ok());
+    }
+
+    #[test]
+    fn escaped_colon_in_attribute_does_not_split_the_component() {
+        let cpe = r"cpe:2.3:a:vendor:product\:with_colon:1.0:*:*:*:*:*:*:*";
+        assert!(Cpe::from_str(cpe).is_ok());
+    }
+
+    #[test]
+    fn wrong_component_count_is_rejected() {
+        assert_eq!(
+            Cpe::from_str("cpe:2.3:a:vendor:product"),
+            Err(CpeParseError::WrongComponentCount(
+                "cpe:2.3:a:vendor:product".to_string(),
+                3
+            ))
+        );
+    }
+
+    #[test]
+    fn illegal_unescaped_character_is_rejected() {
+        assert!(matches!(
+            Cpe::from_str("cpe:2.3:a:vendor:produ!ct:1.0:*:*:*:*:*:*:*"),
+            Err(CpeParseError::IllegalCharacter(_, _))
+        ));
+    }
+
+    #[test]
+    fn invalid_language_is_rejected() {
+        assert!(matches!(
+            Cpe::from_str("cpe:2.3:a:vendor:product:1.0:*:*:english:*:*:*:*"),
+            Err(CpeParseError::InvalidLanguage(_, _))
+        ));
+    }
+
+    #[test]
+    fn legacy_uri_form_parses() {
+        assert!(Cpe::from_str("cpe:/a:vendor:product:1.0").is_ok());
+    }
+
+    #[test]
+    fn unrecognized_prefix_is_rejected() {
+        assert_eq!(
+            Cpe::from_str("not-a-cpe"),
+            Err(CpeParseError::UnrecognizedPrefix("not-a-cpe".to_string()))
+        );
+    }
+}
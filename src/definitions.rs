@@ -0,0 +1,63 @@
+//! [Shared definitions](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3111-document-property---notes)
+//! used across `document` and `vulnerability`.
+
+use serde::{Deserialize, Serialize};
+
+/// [Reference token for product instances](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3214-product-tree-property---full-product-names)
+///
+/// Opaque within a single document - only meaningful when resolved against the `product_tree`
+/// via [`crate::product_tree::ProductTree::trace_product`] or
+/// [`crate::product_tree::ProductTree::resolve`].
+pub type ProductIdT = String;
+
+/// [Reference token for product groups](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3216-product-tree-property---product-groups)
+pub type ProductGroupIdT = String;
+
+/// [Note](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3111-document-property---notes)
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NoteT {
+    pub audience: Option<String>,
+    pub category: NoteCategory,
+    pub text: String,
+    pub title: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteCategory {
+    Description,
+    Details,
+    Faq,
+    General,
+    LegalDisclaimer,
+    Other,
+    Summary,
+}
+
+/// [Reference](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3112-document-property---references)
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReferenceT {
+    pub category: Option<ReferenceCategory>,
+    pub summary: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceCategory {
+    External,
+    #[serde(rename = "self")]
+    SelfRef,
+}
+
+/// [Acknowledgment](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3102-document-property---acknowledgments)
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Acknowledgment {
+    pub names: Option<Vec<String>>,
+    pub organization: Option<String>,
+    pub summary: Option<String>,
+    pub urls: Option<Vec<String>>,
+}
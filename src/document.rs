@@ -0,0 +1,129 @@
+//! [Document property](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#31-document-property)
+
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{Acknowledgment, NoteT, ReferenceT};
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Document {
+    pub acknowledgments: Option<Vec<Acknowledgment>>,
+    pub aggregate_severity: Option<AggregateSeverity>,
+    pub category: String,
+    pub csaf_version: CsafVersion,
+    pub distribution: Option<Distribution>,
+    pub lang: Option<String>,
+    pub notes: Option<Vec<NoteT>>,
+    pub publisher: Publisher,
+    pub references: Option<Vec<ReferenceT>>,
+    pub source_lang: Option<String>,
+    pub title: String,
+    pub tracking: Tracking,
+}
+
+/// [CSAF version the document conforms to](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3116-document-property---csaf-version)
+///
+/// `2.1` tracks the [editor draft](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.1/prose/csaf-v2.1-editor-draft.md);
+/// 2.1-only behavior (currently just [`crate::cpe`] validation) is gated on this variant.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CsafVersion {
+    #[serde(rename = "2.0")]
+    V2_0,
+    #[serde(rename = "2.1")]
+    V2_1,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Publisher {
+    pub category: PublisherCategory,
+    pub contact_details: Option<String>,
+    pub issuing_authority: Option<String>,
+    pub name: String,
+    pub namespace: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PublisherCategory {
+    Coordinator,
+    Discoverer,
+    Other,
+    Translator,
+    User,
+    Vendor,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Tracking {
+    pub aliases: Option<Vec<String>>,
+    pub current_release_date: String,
+    pub generator: Option<Generator>,
+    pub id: String,
+    pub initial_release_date: String,
+    pub revision_history: Vec<Revision>,
+    pub status: TrackingStatus,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackingStatus {
+    Draft,
+    Final,
+    Interim,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub date: String,
+    pub legacy_version: Option<String>,
+    pub number: String,
+    pub summary: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub date: Option<String>,
+    pub engine: Engine,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Engine {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub text: Option<String>,
+    pub tlp: Option<Tlp>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Tlp {
+    pub label: TlpLabel,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TlpLabel {
+    Amber,
+    Green,
+    Red,
+    White,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AggregateSeverity {
+    pub namespace: Option<String>,
+    pub text: String,
+}
@@ -0,0 +1,313 @@
+//! `TryFrom<CveRecord> for Csaf` - converts [MITRE CVE Record Format
+//! 5.0](https://github.com/CVEProject/cve-schema/tree/master/schema/CVE_Record_Format.json)
+//! records into CSAF documents. There is no conversion from a CSAF document back to a CVE record.
+//!
+//! Unlike [`crate::interop::rustsec`] and [`crate::interop::osv`], there is no widely-used crate
+//! for the CVE JSON 5.0 schema, so the small subset of it this crate needs is modeled directly
+//! below rather than pulled in as a dependency.
+
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{NoteCategory, NoteT};
+use crate::document::{
+    CsafVersion, Document, Publisher, PublisherCategory, Revision, Tracking, TrackingStatus,
+};
+use crate::product_tree::{
+    Branch, BranchCategory, FullProductName, ProductIdentificationHelper, ProductTree,
+};
+use crate::vulnerability::{ProductStatus, Score, Vulnerability};
+use crate::Csaf;
+
+/// The subset of a [CVE Record](https://www.cve.org/About/Process) this crate understands:
+/// `cveMetadata` for the identifier, and the first `containers.cna` for affected products,
+/// descriptions and metrics.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CveRecord {
+    pub cve_metadata: CveMetadata,
+    pub containers: Containers,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CveMetadata {
+    pub cve_id: String,
+    pub date_updated: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Containers {
+    pub cna: CnaContainer,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CnaContainer {
+    pub affected: Option<Vec<CnaAffected>>,
+    pub descriptions: Option<Vec<CnaDescription>>,
+    pub metrics: Option<Vec<serde_json::Value>>,
+    pub title: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CnaAffected {
+    pub product: Option<String>,
+    pub vendor: Option<String>,
+    pub cpes: Option<Vec<String>>,
+    pub versions: Option<Vec<CnaVersion>>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CnaVersion {
+    pub status: String,
+    pub version: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CnaDescription {
+    pub lang: String,
+    pub value: String,
+}
+
+/// Conversion only fails when the record carries no `cna.affected` entries to build a
+/// `product_tree` from - a CVE record with nothing affected can't be expressed as VEX.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CveConversionError {
+    #[error("CVE record `{0}` has no containers.cna.affected entries")]
+    NoAffectedProducts(String),
+}
+
+impl TryFrom<CveRecord> for Csaf {
+    type Error = CveConversionError;
+
+    fn try_from(record: CveRecord) -> Result<Self, Self::Error> {
+        let affected = record
+            .containers
+            .cna
+            .affected
+            .filter(|affected| !affected.is_empty())
+            .ok_or_else(|| CveConversionError::NoAffectedProducts(record.cve_metadata.cve_id.clone()))?;
+
+        let mut branches = Vec::new();
+        let mut known_affected = Vec::new();
+        for (index, affected) in affected.iter().enumerate() {
+            let vendor = affected.vendor.clone().unwrap_or_else(|| "n/a".to_string());
+            let product = affected.product.clone().unwrap_or_else(|| "n/a".to_string());
+            let product_id = format!("{}-{index}", record.cve_metadata.cve_id);
+            let helper = affected.cpes.as_ref().and_then(|cpes| cpes.first()).map(|cpe| {
+                ProductIdentificationHelper {
+                    cpe: Some(cpe.clone()),
+                    ..Default::default()
+                }
+            });
+            branches.push(Branch {
+                branches: None,
+                category: BranchCategory::Vendor,
+                name: vendor,
+                product: None,
+            });
+            // Nested directly under its own vendor branch, one vendor branch per affected entry -
+            // CVE Record Format doesn't group affected entries by vendor the way CSAF nests them.
+            #[allow(clippy::unwrap_used)]
+            let vendor_branch = branches.last_mut().unwrap();
+            vendor_branch.branches = Some(vec![Branch {
+                branches: None,
+                category: BranchCategory::ProductName,
+                name: product.clone(),
+                product: Some(FullProductName {
+                    name: product,
+                    product_id: product_id.clone(),
+                    product_identification_helper: helper,
+                }),
+            }]);
+
+            // A `versions` entry with status other than "affected" (e.g. "unaffected",
+            // "unknown") marks a specific version as not vulnerable; absent `versions`, the
+            // `affected` entry as a whole means exactly what its name says.
+            let is_affected = affected.versions.as_ref().map_or(true, |versions| {
+                versions.iter().any(|version| version.status == "affected")
+            });
+            if is_affected {
+                known_affected.push(product_id);
+            }
+        }
+
+        let notes = record
+            .containers
+            .cna
+            .descriptions
+            .map(|descriptions| {
+                descriptions
+                    .into_iter()
+                    .map(|description| NoteT {
+                        audience: None,
+                        category: NoteCategory::Description,
+                        text: description.value,
+                        title: None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|notes| !notes.is_empty());
+
+        // Only the first CVSS v3.x metric is carried over; CVSS v2/v4 metrics and anything past
+        // the first entry are dropped rather than mislabeled. `cvss_v3` must be the inner CVSS
+        // object itself (with `version`/`vectorString`/`baseScore`), not the `{"cvssV3_x": {...}}`
+        // wrapper the CVE record nests it in.
+        let scores = record
+            .containers
+            .cna
+            .metrics
+            .and_then(|metrics| {
+                metrics.into_iter().find_map(|metric| {
+                    metric
+                        .get("cvssV3_1")
+                        .or_else(|| metric.get("cvssV3_0"))
+                        .cloned()
+                })
+            })
+            .map(|cvss_v3| {
+                vec![Score {
+                    cvss_v2: None,
+                    cvss_v3: Some(cvss_v3),
+                    products: known_affected.clone(),
+                }]
+            });
+
+        let title = record
+            .containers
+            .cna
+            .title
+            .clone()
+            .unwrap_or_else(|| record.cve_metadata.cve_id.clone());
+        let date = record
+            .cve_metadata
+            .date_updated
+            .clone()
+            .unwrap_or_default();
+
+        let vulnerability = Vulnerability {
+            cve: Some(record.cve_metadata.cve_id.clone()),
+            notes,
+            product_status: Some(ProductStatus {
+                known_affected: Some(known_affected),
+                ..Default::default()
+            }),
+            scores,
+            title: Some(title.clone()),
+            ..Default::default()
+        };
+
+        Ok(Csaf {
+            document: Document {
+                acknowledgments: None,
+                aggregate_severity: None,
+                category: "csaf_vex".to_string(),
+                csaf_version: CsafVersion::V2_0,
+                distribution: None,
+                lang: None,
+                notes: None,
+                publisher: Publisher {
+                    category: PublisherCategory::Other,
+                    contact_details: None,
+                    issuing_authority: None,
+                    name: "MITRE CVE Program".to_string(),
+                    namespace: "https://www.cve.org".to_string(),
+                },
+                references: None,
+                source_lang: None,
+                title,
+                tracking: Tracking {
+                    aliases: None,
+                    current_release_date: date.clone(),
+                    generator: None,
+                    id: record.cve_metadata.cve_id,
+                    initial_release_date: date.clone(),
+                    revision_history: vec![Revision {
+                        date,
+                        legacy_version: None,
+                        number: "1".to_string(),
+                        summary: "Imported from a CVE JSON 5.0 record.".to_string(),
+                    }],
+                    status: TrackingStatus::Final,
+                    version: "1".to_string(),
+                },
+            },
+            product_tree: Some(ProductTree {
+                branches: Some(branches),
+                full_product_names: None,
+                product_groups: None,
+                relationships: None,
+            }),
+            vulnerabilities: Some(vec![vulnerability]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_with_metric(metric: serde_json::Value) -> CveRecord {
+        CveRecord {
+            cve_metadata: CveMetadata {
+                cve_id: "CVE-2024-0001".to_string(),
+                date_updated: Some("2024-01-01T00:00:00Z".to_string()),
+            },
+            containers: Containers {
+                cna: CnaContainer {
+                    affected: Some(vec![CnaAffected {
+                        product: Some("widget".to_string()),
+                        vendor: Some("acme".to_string()),
+                        cpes: None,
+                        versions: None,
+                    }]),
+                    descriptions: None,
+                    metrics: Some(vec![metric]),
+                    title: None,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn cvss_v3_score_is_unwrapped_from_its_metric_container() {
+        let record = record_with_metric(serde_json::json!({
+            "cvssV3_1": {"version": "3.1", "vectorString": "CVSS:3.1/AV:N", "baseScore": 9.8},
+        }));
+        let csaf: Csaf = record.try_into().unwrap();
+        let score = csaf.vulnerabilities.unwrap()[0].scores.clone().unwrap();
+        assert_eq!(score[0].cvss_v3, Some(serde_json::json!({"version": "3.1", "vectorString": "CVSS:3.1/AV:N", "baseScore": 9.8})));
+    }
+
+    #[test]
+    fn non_cvss_v3_metric_yields_no_score() {
+        let record = record_with_metric(serde_json::json!({
+            "cvssV2_0": {"version": "2.0", "baseScore": 7.5},
+        }));
+        let csaf: Csaf = record.try_into().unwrap();
+        assert!(csaf.vulnerabilities.unwrap()[0].scores.is_none());
+    }
+
+    #[test]
+    fn unaffected_version_is_excluded_from_known_affected() {
+        let mut record = record_with_metric(serde_json::json!({}));
+        record.containers.cna.affected = Some(vec![CnaAffected {
+            product: Some("widget".to_string()),
+            vendor: Some("acme".to_string()),
+            cpes: None,
+            versions: Some(vec![CnaVersion {
+                status: "unaffected".to_string(),
+                version: "2.0".to_string(),
+            }]),
+        }]);
+        let csaf: Csaf = record.try_into().unwrap();
+        let status = csaf.vulnerabilities.unwrap()[0].product_status.clone().unwrap();
+        assert!(status.known_affected.unwrap_or_default().is_empty());
+    }
+}
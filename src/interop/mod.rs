@@ -0,0 +1,5 @@
+//! Conversions between CSAF and other vulnerability advisory formats.
+
+pub mod cve;
+pub mod osv;
+pub mod rustsec;
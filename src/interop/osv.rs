@@ -0,0 +1,250 @@
+//! `From<osv::schema::Vulnerability> for Csaf` and the reverse `TryFrom<Csaf> for Vec<Osv>` -
+//! bridges [OSV](https://ossf.github.io/osv-schema/) records into CSAF documents and back.
+
+use osv::schema::Vulnerability as Osv;
+
+use crate::definitions::{NoteCategory, NoteT};
+use crate::document::{
+    CsafVersion, Document, Publisher, PublisherCategory, Revision, Tracking, TrackingStatus,
+};
+use crate::product_tree::{Branch, BranchCategory, FullProductName, ProductIdentificationHelper, ProductTree};
+use crate::vulnerability::{Id, ProductStatus, Vulnerability};
+use crate::Csaf;
+
+impl From<Osv> for Csaf {
+    fn from(osv: Osv) -> Self {
+        let date = osv.modified.to_string();
+        let title = osv.summary.clone().unwrap_or_else(|| osv.id.clone());
+
+        let mut branches = Vec::new();
+        let mut known_affected = Vec::new();
+        for (index, affected) in osv.affected.iter().flatten().enumerate() {
+            let Some(package) = &affected.package else {
+                continue;
+            };
+            let product_id = format!("{}-{index}", osv.id);
+            let purl = package
+                .purl
+                .clone()
+                .unwrap_or_else(|| format!("pkg:{}/{}", package.ecosystem.to_lowercase(), package.name));
+            branches.push(Branch {
+                branches: None,
+                category: BranchCategory::ProductName,
+                name: package.name.clone(),
+                product: Some(FullProductName {
+                    name: package.name.clone(),
+                    product_id: product_id.clone(),
+                    product_identification_helper: Some(ProductIdentificationHelper {
+                        purl: Some(purl),
+                        ..Default::default()
+                    }),
+                }),
+            });
+            known_affected.push(product_id);
+        }
+
+        let vulnerability = Vulnerability {
+            ids: (!osv.aliases.is_empty()).then(|| {
+                osv.aliases
+                    .iter()
+                    .map(|alias| Id {
+                        system_name: "OSV Alias".to_string(),
+                        text: alias.clone(),
+                    })
+                    .collect()
+            }),
+            notes: osv.details.clone().map(|details| {
+                vec![NoteT {
+                    audience: None,
+                    category: NoteCategory::Details,
+                    text: details,
+                    title: None,
+                }]
+            }),
+            product_status: Some(ProductStatus {
+                known_affected: Some(known_affected),
+                ..Default::default()
+            }),
+            title: Some(title.clone()),
+            ..Default::default()
+        };
+
+        Csaf {
+            document: Document {
+                acknowledgments: None,
+                aggregate_severity: None,
+                category: "csaf_vex".to_string(),
+                csaf_version: CsafVersion::V2_0,
+                distribution: None,
+                lang: None,
+                notes: None,
+                publisher: Publisher {
+                    category: PublisherCategory::Other,
+                    contact_details: None,
+                    issuing_authority: None,
+                    name: "OSV".to_string(),
+                    namespace: "https://osv.dev".to_string(),
+                },
+                references: None,
+                source_lang: None,
+                title,
+                tracking: Tracking {
+                    aliases: (!osv.aliases.is_empty()).then(|| osv.aliases.clone()),
+                    current_release_date: date.clone(),
+                    generator: None,
+                    id: osv.id.clone(),
+                    initial_release_date: date.clone(),
+                    revision_history: vec![Revision {
+                        date,
+                        legacy_version: None,
+                        number: "1".to_string(),
+                        summary: "Imported from the OSV database.".to_string(),
+                    }],
+                    status: TrackingStatus::Final,
+                    version: "1".to_string(),
+                },
+            },
+            product_tree: Some(ProductTree {
+                branches: Some(branches),
+                full_product_names: None,
+                product_groups: None,
+                relationships: None,
+            }),
+            vulnerabilities: Some(vec![vulnerability]),
+        }
+    }
+}
+
+/// Conversion only fails when `document.tracking.current_release_date` isn't a valid RFC 3339
+/// timestamp, since OSV's `modified` field requires one.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum OsvConversionError {
+    #[error("document.tracking.current_release_date `{0}` is not a valid RFC 3339 timestamp")]
+    InvalidReleaseDate(String),
+}
+
+/// The reverse direction: one [`Osv`] record per `vulnerability`, since OSV has no notion of a
+/// single document bundling several CVEs the way CSAF does.
+impl TryFrom<Csaf> for Vec<Osv> {
+    type Error = OsvConversionError;
+
+    fn try_from(csaf: Csaf) -> Result<Self, Self::Error> {
+        let modified = csaf
+            .document
+            .tracking
+            .current_release_date
+            .parse()
+            .map_err(|_| OsvConversionError::InvalidReleaseDate(csaf.document.tracking.current_release_date.clone()))?;
+
+        let product_tree = csaf.product_tree;
+        Ok(csaf
+            .vulnerabilities
+            .into_iter()
+            .flatten()
+            .map(|vulnerability| {
+                let affected = vulnerability
+                    .product_status
+                    .iter()
+                    .flat_map(|status| status.known_affected.iter().flatten())
+                    .filter_map(|product_id| {
+                        let resolved = product_tree.as_ref()?.resolve(product_id).ok()?;
+                        Some(osv::schema::Affected {
+                            package: Some(osv::schema::Package {
+                                ecosystem: "Generic".to_string(),
+                                name: resolved.full_product_name.name.clone(),
+                                purl: resolved.purl.as_ref().map(ToString::to_string),
+                            }),
+                            ..Default::default()
+                        })
+                    })
+                    .collect();
+
+                Osv {
+                    id: vulnerability
+                        .cve
+                        .clone()
+                        .unwrap_or_else(|| csaf.document.tracking.id.clone()),
+                    summary: vulnerability.title.clone(),
+                    details: vulnerability
+                        .notes
+                        .iter()
+                        .flatten()
+                        .find(|note| note.category == NoteCategory::Details)
+                        .map(|note| note.text.clone()),
+                    modified,
+                    aliases: vulnerability.cve.clone().into_iter().collect(),
+                    affected: Some(affected),
+                    ..Default::default()
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal_csaf(release_date: &str) -> Csaf {
+        Csaf {
+            document: Document {
+                acknowledgments: None,
+                aggregate_severity: None,
+                category: "csaf_vex".to_string(),
+                csaf_version: CsafVersion::V2_0,
+                distribution: None,
+                lang: None,
+                notes: None,
+                publisher: Publisher {
+                    category: PublisherCategory::Other,
+                    contact_details: None,
+                    issuing_authority: None,
+                    name: "Test".to_string(),
+                    namespace: "https://example.com".to_string(),
+                },
+                references: None,
+                source_lang: None,
+                title: "Test advisory".to_string(),
+                tracking: Tracking {
+                    aliases: None,
+                    current_release_date: release_date.to_string(),
+                    generator: None,
+                    id: "TEST-1".to_string(),
+                    initial_release_date: release_date.to_string(),
+                    revision_history: vec![Revision {
+                        date: release_date.to_string(),
+                        legacy_version: None,
+                        number: "1".to_string(),
+                        summary: "Initial version.".to_string(),
+                    }],
+                    status: TrackingStatus::Final,
+                    version: "1".to_string(),
+                },
+            },
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                cve: Some("CVE-2024-0001".to_string()),
+                title: Some("Test vuln".to_string()),
+                ..Default::default()
+            }]),
+        }
+    }
+
+    #[test]
+    fn reverse_conversion_parses_a_valid_release_date() {
+        let csaf = minimal_csaf("2024-01-01T00:00:00Z");
+        let osvs: Vec<Osv> = csaf.try_into().unwrap();
+        assert_eq!(osvs.len(), 1);
+        assert_eq!(osvs[0].modified.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn reverse_conversion_rejects_an_unparseable_release_date() {
+        let csaf = minimal_csaf("not-a-date");
+        let result: Result<Vec<Osv>, _> = csaf.try_into();
+        assert_eq!(
+            result.unwrap_err(),
+            OsvConversionError::InvalidReleaseDate("not-a-date".to_string())
+        );
+    }
+}
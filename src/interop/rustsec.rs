@@ -0,0 +1,117 @@
+//! `From<rustsec::Advisory> for Csaf` - bridges [RustSec](https://rustsec.org/) advisories
+//! (as consumed from the [RustSec advisory database](https://github.com/RustSec/advisory-db))
+//! into CSAF documents.
+
+use crate::definitions::{NoteCategory, NoteT, ReferenceCategory, ReferenceT};
+use crate::document::{
+    CsafVersion, Document, Publisher, PublisherCategory, Revision, Tracking, TrackingStatus,
+};
+use crate::product_tree::{
+    Branch, BranchCategory, FullProductName, ProductIdentificationHelper, ProductTree,
+};
+use crate::vulnerability::{ProductStatus, Vulnerability};
+use crate::Csaf;
+
+impl From<rustsec::Advisory> for Csaf {
+    fn from(advisory: rustsec::Advisory) -> Self {
+        let metadata = &advisory.metadata;
+        let package = metadata.package.to_string();
+        let product_id = format!("{package}:{}", metadata.id);
+        let date = metadata.date.to_string();
+
+        let purl = format!("pkg:cargo/{package}");
+        let product = FullProductName {
+            name: package.clone(),
+            product_id: product_id.clone(),
+            product_identification_helper: Some(ProductIdentificationHelper {
+                purl: Some(purl),
+                ..Default::default()
+            }),
+        };
+
+        let notes = (!metadata.description.is_empty()).then(|| {
+            vec![NoteT {
+                audience: None,
+                category: NoteCategory::Description,
+                text: metadata.description.clone(),
+                title: None,
+            }]
+        });
+
+        let references = (!metadata.references.is_empty())
+            .then(|| {
+                metadata
+                    .references
+                    .iter()
+                    .map(|url| ReferenceT {
+                        category: Some(ReferenceCategory::External),
+                        summary: url.to_string(),
+                        url: url.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|refs| !refs.is_empty());
+
+        let vulnerability = Vulnerability {
+            cve: metadata.id.is_cve().then(|| metadata.id.to_string()),
+            notes,
+            product_status: Some(ProductStatus {
+                known_affected: Some(vec![product_id.clone()]),
+                ..Default::default()
+            }),
+            references,
+            title: Some(metadata.title.clone()),
+            ..Default::default()
+        };
+
+        Csaf {
+            document: Document {
+                acknowledgments: None,
+                aggregate_severity: None,
+                category: "csaf_vex".to_string(),
+                csaf_version: CsafVersion::V2_0,
+                distribution: None,
+                lang: None,
+                notes: None,
+                publisher: Publisher {
+                    category: PublisherCategory::Coordinator,
+                    contact_details: None,
+                    issuing_authority: None,
+                    name: "RustSec Advisory Database".to_string(),
+                    namespace: "https://rustsec.org".to_string(),
+                },
+                references: None,
+                source_lang: None,
+                title: metadata.title.clone(),
+                tracking: Tracking {
+                    aliases: (!metadata.aliases.is_empty())
+                        .then(|| metadata.aliases.iter().map(ToString::to_string).collect()),
+                    current_release_date: date.clone(),
+                    generator: None,
+                    id: metadata.id.to_string(),
+                    initial_release_date: date.clone(),
+                    revision_history: vec![Revision {
+                        date,
+                        legacy_version: None,
+                        number: "1".to_string(),
+                        summary: "Imported from the RustSec advisory database.".to_string(),
+                    }],
+                    status: TrackingStatus::Final,
+                    version: "1".to_string(),
+                },
+            },
+            product_tree: Some(ProductTree {
+                branches: Some(vec![Branch {
+                    branches: None,
+                    category: BranchCategory::ProductName,
+                    name: package,
+                    product: Some(product),
+                }]),
+                full_product_names: None,
+                product_groups: None,
+                relationships: None,
+            }),
+            vulnerabilities: Some(vec![vulnerability]),
+        }
+    }
+}
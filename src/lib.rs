@@ -13,13 +13,17 @@
 
 use serde::{Deserialize, Serialize};
 
+use definitions::ProductIdT;
 use document::Document;
 use product_tree::ProductTree;
-use vulnerability::Vulnerability;
+use vulnerability::{VexFinding, VexStatus, Vulnerability};
 
+pub mod builder;
+pub mod cpe;
 pub mod definitions;
 pub mod document;
 pub mod product_tree;
+pub mod validation;
 pub mod vulnerability;
 
 pub mod interop;
@@ -35,6 +39,61 @@ pub struct Csaf {
     pub vulnerabilities: Option<Vec<Vulnerability>>,
 }
 
+impl Csaf {
+    /// For every vulnerability, classify `product_id` into a VEX status bucket and collect the
+    /// remediations, flags and threats that apply to it - including those targeted via a
+    /// `product_tree.product_groups` group rather than `product_id` directly. Products not
+    /// mentioned by a given vulnerability's `product_status` are simply absent from the result,
+    /// rather than reported as e.g. "not affected".
+    pub fn status_for_product(&self, product_id: &ProductIdT) -> Vec<VexFinding<'_>> {
+        let product_groups = self
+            .product_tree
+            .as_ref()
+            .and_then(|tree| tree.product_groups.as_deref())
+            .unwrap_or_default();
+        self.vulnerabilities
+            .iter()
+            .flatten()
+            .filter_map(|vulnerability| vulnerability.vex_finding(product_id, product_groups))
+            .collect()
+    }
+
+    /// Like [`Csaf::status_for_product`], but looks the product up by PURL via
+    /// [`product_tree::ProductTree::product_id_by_purl`] instead of requiring the caller to
+    /// already know its opaque `product_id`. Returns an empty vector if no product in the
+    /// document carries this PURL.
+    pub fn status_for_purl(&self, purl: &str) -> Vec<VexFinding<'_>> {
+        self.product_tree
+            .as_ref()
+            .and_then(|tree| tree.product_id_by_purl(purl))
+            .map(|product_id| self.status_for_product(product_id))
+            .unwrap_or_default()
+    }
+
+    /// Like [`Csaf::status_for_product`], but looks the product up by CPE via
+    /// [`product_tree::ProductTree::product_id_by_cpe`] instead of requiring the caller to
+    /// already know its opaque `product_id`. Returns an empty vector if no product in the
+    /// document carries this CPE.
+    pub fn status_for_cpe(&self, cpe: &str) -> Vec<VexFinding<'_>> {
+        self.product_tree
+            .as_ref()
+            .and_then(|tree| tree.product_id_by_cpe(cpe))
+            .map(|product_id| self.status_for_product(product_id))
+            .unwrap_or_default()
+    }
+
+    /// The reverse of [`Csaf::status_for_product`]: every product_id this document places into a
+    /// VEX status bucket for the vulnerability identified by `cve`, together with that bucket.
+    pub fn affecting_products(&self, cve: &str) -> Vec<(&ProductIdT, VexStatus)> {
+        self.vulnerabilities
+            .iter()
+            .flatten()
+            .filter(|vulnerability| vulnerability.cve.as_deref() == Some(cve))
+            .flat_map(Vulnerability::affected_product_ids)
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,378 @@
+//! [Product tree property](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#32-product-tree-property)
+
+use std::collections::HashSet;
+
+use packageurl::PackageUrl;
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::ProductIdT;
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProductTree {
+    pub branches: Option<Vec<Branch>>,
+    pub full_product_names: Option<Vec<FullProductName>>,
+    pub product_groups: Option<Vec<ProductGroup>>,
+    pub relationships: Option<Vec<Relationship>>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Branch {
+    pub branches: Option<Vec<Branch>>,
+    pub category: BranchCategory,
+    pub name: String,
+    pub product: Option<FullProductName>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BranchCategory {
+    Architecture,
+    HostName,
+    Language,
+    Legacy,
+    OperatingSystem,
+    PackageVersion,
+    PatchLevel,
+    ProductFamily,
+    ProductName,
+    ProductVersion,
+    ProductVersionRange,
+    ServicePack,
+    Specification,
+    Vendor,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FullProductName {
+    pub name: String,
+    pub product_id: ProductIdT,
+    pub product_identification_helper: Option<ProductIdentificationHelper>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProductIdentificationHelper {
+    pub cpe: Option<String>,
+    pub hashes: Option<Vec<Hash>>,
+    pub model_numbers: Option<Vec<String>>,
+    pub purl: Option<String>,
+    pub sbom_urls: Option<Vec<String>>,
+    pub serial_numbers: Option<Vec<String>>,
+    pub skus: Option<Vec<String>>,
+    pub x_generic_uris: Option<Vec<GenericUri>>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Hash {
+    pub file_hashes: Vec<FileHash>,
+    pub file_name: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileHash {
+    pub algorithm: String,
+    pub value: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GenericUri {
+    pub namespace: String,
+    pub uri: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProductGroup {
+    pub group_id: String,
+    pub product_ids: Vec<ProductIdT>,
+    pub summary: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Relationship {
+    pub category: RelationshipCategory,
+    pub full_product_name: FullProductName,
+    pub product_reference: ProductIdT,
+    pub relates_to_product_reference: ProductIdT,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipCategory {
+    DefaultComponentOf,
+    ExternalComponentOf,
+    InstalledOn,
+    InstalledWith,
+    OptionalComponentOf,
+}
+
+/// Errors produced while resolving a [`ProductIdT`] against a [`ProductTree`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ResolveError {
+    /// The `product_id` is not defined anywhere in the product tree: not as the leaf of a
+    /// branch, not as a top-level `full_product_names` entry, and not as the `full_product_name`
+    /// of a `relationship`.
+    #[error("product_id `{0}` is not defined in the product tree")]
+    DanglingProductId(ProductIdT),
+    /// Following `relates_to_product_reference` chains did not terminate within the number of
+    /// relationships present in the tree, which means the relationships form a cycle.
+    #[error("relationship chain for product_id `{0}` contains a cycle")]
+    RelationshipCycle(ProductIdT),
+}
+
+/// The fully-resolved identity of a product: its full product name, the branch path that led to
+/// it (empty when the product was only ever declared at the top level or via a relationship),
+/// and the parsed product identification helper, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedProduct<'a> {
+    pub full_product_name: &'a FullProductName,
+    pub branch_path: Vec<&'a Branch>,
+    pub cpe: Option<&'a str>,
+    pub purl: Option<PackageUrl<'a>>,
+}
+
+impl ProductTree {
+    /// Walk the recursive `branches` tree and return the ordered list of branch nodes (e.g.
+    /// vendor -> product_family -> product_name -> version) whose leaf `full_product_name`
+    /// carries the given `product_id`.
+    ///
+    /// Returns an empty vector both when `product_id` isn't found under `branches` at all and
+    /// when it is only reachable via `full_product_names` or a `relationship` - callers that
+    /// need to distinguish those cases, or that want relationships followed, should use
+    /// [`ProductTree::resolve`] instead.
+    pub fn trace_product(&self, product_id: &ProductIdT) -> Vec<&Branch> {
+        for root in self.branches.iter().flatten() {
+            if let Some(path) = root.trace(product_id) {
+                return path;
+            }
+        }
+        Vec::new()
+    }
+
+    /// Resolve a `product_id` to its full identity: the branch path (if any), the
+    /// `full_product_name`, and its parsed `cpe`/`purl`.
+    ///
+    /// When `product_id` is only defined via a [`Relationship`], the relationship's own
+    /// `full_product_name` is returned (it already carries the combined identity - e.g. "component
+    /// X installed on product Y") and `branch_path` is empty.
+    pub fn resolve(&self, product_id: &ProductIdT) -> Result<ResolvedProduct<'_>, ResolveError> {
+        let branch_path = self.trace_product(product_id);
+        let full_product_name = if let Some(leaf) = branch_path.last() {
+            // `trace_product` only returns a path ending at a branch with a matching product.
+            #[allow(clippy::unwrap_used)]
+            leaf.product.as_ref().unwrap()
+        } else if let Some(top_level) = self
+            .full_product_names
+            .iter()
+            .flatten()
+            .find(|fpn| &fpn.product_id == product_id)
+        {
+            top_level
+        } else if let Some(relationship) = self.find_relationship(product_id)? {
+            &relationship.full_product_name
+        } else {
+            return Err(ResolveError::DanglingProductId(product_id.clone()));
+        };
+
+        let helper = full_product_name.product_identification_helper.as_ref();
+        Ok(ResolvedProduct {
+            full_product_name,
+            branch_path,
+            cpe: helper.and_then(|h| h.cpe.as_deref()),
+            purl: helper
+                .and_then(|h| h.purl.as_deref())
+                .and_then(|purl| PackageUrl::from_string(purl).ok()),
+        })
+    }
+
+    /// Every `product_id` defined anywhere in this tree: branch leaves, top-level
+    /// `full_product_names`, and relationship `full_product_name`s. Used by
+    /// [`crate::validation`] to check for duplicate definitions.
+    pub fn all_defined_product_ids(&self) -> Vec<&ProductIdT> {
+        let mut out = Vec::new();
+        for root in self.branches.iter().flatten() {
+            root.collect_product_ids(&mut out);
+        }
+        out.extend(self.full_product_names.iter().flatten().map(|fpn| &fpn.product_id));
+        out.extend(
+            self.relationships
+                .iter()
+                .flatten()
+                .map(|relationship| &relationship.full_product_name.product_id),
+        );
+        out
+    }
+
+    /// Every [`FullProductName`] defined anywhere in this tree - branch leaves, top-level
+    /// `full_product_names`, and relationship `full_product_name`s - paired with the RFC 6901
+    /// JSON pointer to where it lives in the document.
+    pub fn all_full_product_names(&self) -> Vec<(&FullProductName, String)> {
+        let mut out = Vec::new();
+        for (index, root) in self.branches.iter().flatten().enumerate() {
+            root.collect_full_product_names(format!("/product_tree/branches/{index}"), &mut out);
+        }
+        out.extend(
+            self.full_product_names
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(index, fpn)| (fpn, format!("/product_tree/full_product_names/{index}"))),
+        );
+        out.extend(self.relationships.iter().flatten().enumerate().map(|(index, relationship)| {
+            (
+                &relationship.full_product_name,
+                format!("/product_tree/relationships/{index}/full_product_name"),
+            )
+        }));
+        out
+    }
+
+    /// Find the `product_id` whose `product_identification_helper.purl` matches `purl` exactly.
+    /// Used to let callers query [`crate::Csaf::status_for_product`]-style APIs by PURL instead
+    /// of by the document's opaque `product_id`.
+    pub fn product_id_by_purl(&self, purl: &str) -> Option<&ProductIdT> {
+        self.all_full_product_names().into_iter().find_map(|(fpn, _)| {
+            let helper_purl = fpn.product_identification_helper.as_ref()?.purl.as_deref()?;
+            (helper_purl == purl).then_some(&fpn.product_id)
+        })
+    }
+
+    /// Find the `product_id` whose `product_identification_helper.cpe` matches `cpe` exactly.
+    pub fn product_id_by_cpe(&self, cpe: &str) -> Option<&ProductIdT> {
+        self.all_full_product_names().into_iter().find_map(|(fpn, _)| {
+            let helper_cpe = fpn.product_identification_helper.as_ref()?.cpe.as_deref()?;
+            (helper_cpe == cpe).then_some(&fpn.product_id)
+        })
+    }
+
+    /// Find the [`Relationship`] whose combined `full_product_name` carries `product_id`,
+    /// validating that its `relates_to_product_reference` chain (relationships can relate a
+    /// product to another product that is itself only defined by a relationship) terminates
+    /// rather than cycling back on itself.
+    fn find_relationship(&self, product_id: &ProductIdT) -> Result<Option<&Relationship>, ResolveError> {
+        let relationships = self.relationships.as_deref().unwrap_or_default();
+        let Some(relationship) = relationships
+            .iter()
+            .find(|r| &r.full_product_name.product_id == product_id)
+        else {
+            return Ok(None);
+        };
+
+        let mut current = &relationship.relates_to_product_reference;
+        let mut seen: HashSet<&ProductIdT> = HashSet::from([product_id]);
+        while let Some(next) = relationships
+            .iter()
+            .find(|r| &r.full_product_name.product_id == current)
+        {
+            if !seen.insert(current) {
+                return Err(ResolveError::RelationshipCycle(product_id.clone()));
+            }
+            current = &next.relates_to_product_reference;
+        }
+
+        Ok(Some(relationship))
+    }
+}
+
+impl Branch {
+    fn collect_product_ids<'a>(&'a self, out: &mut Vec<&'a ProductIdT>) {
+        if let Some(product) = &self.product {
+            out.push(&product.product_id);
+        }
+        for child in self.branches.iter().flatten() {
+            child.collect_product_ids(out);
+        }
+    }
+
+    fn collect_full_product_names<'a>(&'a self, pointer: String, out: &mut Vec<(&'a FullProductName, String)>) {
+        if let Some(product) = &self.product {
+            out.push((product, format!("{pointer}/product")));
+        }
+        for (index, child) in self.branches.iter().flatten().enumerate() {
+            child.collect_full_product_names(format!("{pointer}/branches/{index}"), out);
+        }
+    }
+
+    fn trace(&self, product_id: &ProductIdT) -> Option<Vec<&Branch>> {
+        if let Some(product) = &self.product {
+            if &product.product_id == product_id {
+                return Some(vec![self]);
+            }
+        }
+        for child in self.branches.iter().flatten() {
+            if let Some(mut path) = child.trace(product_id) {
+                path.insert(0, self);
+                return Some(path);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relationship(product_id: &str, relates_to: &str) -> Relationship {
+        Relationship {
+            category: RelationshipCategory::InstalledOn,
+            full_product_name: FullProductName {
+                name: product_id.to_string(),
+                product_id: product_id.to_string(),
+                product_identification_helper: None,
+            },
+            product_reference: format!("component-of-{product_id}"),
+            relates_to_product_reference: relates_to.to_string(),
+        }
+    }
+
+    #[test]
+    fn relationship_cycle_is_detected_rather_than_looping_forever() {
+        let tree = ProductTree {
+            branches: None,
+            full_product_names: None,
+            product_groups: None,
+            relationships: Some(vec![relationship("A", "B"), relationship("B", "A")]),
+        };
+
+        assert_eq!(tree.resolve(&"A".to_string()), Err(ResolveError::RelationshipCycle("A".to_string())));
+    }
+
+    #[test]
+    fn relationship_chain_to_a_branch_defined_product_resolves() {
+        let tree = ProductTree {
+            branches: Some(vec![Branch {
+                branches: None,
+                category: BranchCategory::ProductName,
+                name: "Base".to_string(),
+                product: Some(FullProductName {
+                    name: "Base".to_string(),
+                    product_id: "BASE".to_string(),
+                    product_identification_helper: None,
+                }),
+            }]),
+            full_product_names: None,
+            product_groups: None,
+            relationships: Some(vec![relationship("COMPONENT", "BASE")]),
+        };
+
+        let resolved = tree.resolve(&"COMPONENT".to_string()).unwrap();
+        assert_eq!(resolved.full_product_name.product_id, "COMPONENT");
+    }
+
+    #[test]
+    fn dangling_product_id_is_an_error() {
+        let tree = ProductTree::default();
+        assert_eq!(
+            tree.resolve(&"missing".to_string()),
+            Err(ResolveError::DanglingProductId("missing".to_string()))
+        );
+    }
+}
@@ -0,0 +1,485 @@
+//! Conformance validation against the CSAF spec's [mandatory tests and profile-specific
+//! requirements](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#6-conformance).
+//!
+//! This crate is deliberately less strict than the spec when deserializing - invalid CSAF may
+//! still parse, and this crate can be used to generate invalid CSAF. [`Csaf::validate`] is the
+//! opt-in check for callers who need spec conformance, e.g. before publishing a document.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::cpe::Cpe;
+use crate::definitions::ProductIdT;
+use crate::document::CsafVersion;
+use crate::Csaf;
+
+/// Strictness level to validate against, mirroring how the official tooling separates mandatory
+/// tests from the additional requirements each profile layers on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Only the tests that apply to every CSAF document, regardless of its `document.category`.
+    Mandatory,
+    /// [VEX profile](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#41-profile-1-generic-csaf): requires product status or remediation information.
+    Vex,
+    /// [Security advisory profile](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#45-profile-5-security-advisory): requires notes and scores.
+    SecurityAdvisory,
+}
+
+/// A single failed conformance test.
+///
+/// `path` is a JSON pointer (RFC 6901) into the document locating the offending value, and
+/// `test` is the identifier of the spec test that failed, e.g. `"6.1.1.1"`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("[{test}] {message} (at {path})")]
+pub struct ValidationError {
+    pub test: &'static str,
+    pub path: String,
+    pub message: String,
+}
+
+impl Csaf {
+    /// Validate this document against `profile`, returning every failed test rather than
+    /// stopping at the first one.
+    pub fn validate(&self, profile: Profile) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        self.check_product_ids_defined(&mut errors);
+        self.check_product_ids_unique(&mut errors);
+        self.check_contradicting_product_status(&mut errors);
+        self.check_revision_history(&mut errors);
+        if self.document.csaf_version == CsafVersion::V2_1 {
+            self.check_cpe_syntax(&mut errors);
+        }
+
+        match profile {
+            Profile::Mandatory => {}
+            Profile::Vex => self.check_vex_profile(&mut errors),
+            Profile::SecurityAdvisory => self.check_security_advisory_profile(&mut errors),
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// [6.1.1.1 Missing Definition of Product ID](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#61111-missing-definition-of-product-id):
+    /// every `ProductIdT` referenced from `vulnerabilities` must be defined in `product_tree`.
+    fn check_product_ids_defined(&self, errors: &mut Vec<ValidationError>) {
+        let product_tree = self.product_tree.as_ref();
+        for (v_idx, referenced, pointer) in self.referenced_product_ids() {
+            let defined = product_tree.is_some_and(|tree| tree.resolve(referenced).is_ok());
+            if !defined {
+                errors.push(ValidationError {
+                    test: "6.1.1.1",
+                    path: pointer,
+                    message: format!(
+                        "product_id `{referenced}` referenced by vulnerabilities[{v_idx}] is not defined in product_tree"
+                    ),
+                });
+            }
+        }
+    }
+
+    /// [6.1.1.2 Multiple Definition of Product ID](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#61112-multiple-definition-of-product-id):
+    /// a `product_id` must be defined at most once across `branches`, `full_product_names` and
+    /// `relationships`.
+    fn check_product_ids_unique(&self, errors: &mut Vec<ValidationError>) {
+        let Some(tree) = &self.product_tree else {
+            return;
+        };
+        let mut seen = HashSet::new();
+        for product_id in tree.all_defined_product_ids() {
+            if !seen.insert(product_id) {
+                errors.push(ValidationError {
+                    test: "6.1.1.2",
+                    path: "/product_tree".to_string(),
+                    message: format!("product_id `{product_id}` is defined more than once"),
+                });
+            }
+        }
+    }
+
+    /// [6.1.1.3 Contradicting Product Status](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#61113-contradicting-product-status):
+    /// a product may not appear in both `known_affected` and `known_not_affected` for the same
+    /// vulnerability.
+    fn check_contradicting_product_status(&self, errors: &mut Vec<ValidationError>) {
+        for (v_idx, vulnerability) in self.vulnerabilities.iter().flatten().enumerate() {
+            let Some(status) = &vulnerability.product_status else {
+                continue;
+            };
+            let affected: HashSet<&ProductIdT> =
+                status.known_affected.iter().flatten().collect();
+            let not_affected: HashSet<&ProductIdT> =
+                status.known_not_affected.iter().flatten().collect();
+            for product_id in affected.intersection(&not_affected) {
+                errors.push(ValidationError {
+                    test: "6.1.1.3",
+                    path: format!("/vulnerabilities/{v_idx}/product_status"),
+                    message: format!(
+                        "product_id `{product_id}` is listed as both known_affected and known_not_affected"
+                    ),
+                });
+            }
+        }
+    }
+
+    /// [6.1.1.4/6.1.1.5 Revision History](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#61114-multiple-definition-in-revision-history):
+    /// revision numbers must be unique, and the latest one must match `tracking.version`.
+    fn check_revision_history(&self, errors: &mut Vec<ValidationError>) {
+        let history = &self.document.tracking.revision_history;
+        let mut seen = HashSet::new();
+        for (idx, revision) in history.iter().enumerate() {
+            if !seen.insert(&revision.number) {
+                errors.push(ValidationError {
+                    test: "6.1.1.4",
+                    path: format!("/document/tracking/revision_history/{idx}/number"),
+                    message: format!("revision number `{}` is used more than once", revision.number),
+                });
+            }
+        }
+        // `revision_history` is defined to be listed in chronological order, so the latest
+        // revision is the last entry - not the lexicographic max of `number` (which would put
+        // "2" ahead of "10").
+        let latest = history.last().map(|revision| &revision.number);
+        if let Some(latest) = latest {
+            if latest != &self.document.tracking.version {
+                errors.push(ValidationError {
+                    test: "6.1.1.5",
+                    path: "/document/tracking/version".to_string(),
+                    message: format!(
+                        "tracking.version `{}` does not match latest revision_history number `{latest}`",
+                        self.document.tracking.version
+                    ),
+                });
+            }
+        }
+    }
+
+    /// CSAF 2.1: every `product_identification_helper.cpe` must be a syntactically well-formed
+    /// CPE 2.3 formatted string or legacy CPE 2.2 URI.
+    fn check_cpe_syntax(&self, errors: &mut Vec<ValidationError>) {
+        let Some(tree) = &self.product_tree else {
+            return;
+        };
+        for (full_product_name, pointer) in tree.all_full_product_names() {
+            let Some(cpe) = full_product_name
+                .product_identification_helper
+                .as_ref()
+                .and_then(|helper| helper.cpe.as_deref())
+            else {
+                continue;
+            };
+            if let Err(parse_error) = Cpe::from_str(cpe) {
+                errors.push(ValidationError {
+                    test: "6.1.1.6",
+                    path: format!("{pointer}/product_identification_helper/cpe"),
+                    message: parse_error.to_string(),
+                });
+            }
+        }
+    }
+
+    /// [4.1 VEX profile](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#41-profile-1-generic-csaf):
+    /// at least one vulnerability must carry `product_status` or a `remediation`.
+    fn check_vex_profile(&self, errors: &mut Vec<ValidationError>) {
+        let satisfied = self.vulnerabilities.iter().flatten().any(|vulnerability| {
+            vulnerability.product_status.is_some() || vulnerability.remediations.is_some()
+        });
+        if !satisfied {
+            errors.push(ValidationError {
+                test: "4.1",
+                path: "/vulnerabilities".to_string(),
+                message: "VEX profile requires at least one vulnerability with product_status or a remediation".to_string(),
+            });
+        }
+    }
+
+    /// [4.5 Security advisory profile](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#45-profile-5-security-advisory):
+    /// requires notes and scores.
+    fn check_security_advisory_profile(&self, errors: &mut Vec<ValidationError>) {
+        if self.document.notes.as_deref().unwrap_or_default().is_empty() {
+            errors.push(ValidationError {
+                test: "4.5",
+                path: "/document/notes".to_string(),
+                message: "security advisory profile requires document.notes".to_string(),
+            });
+        }
+        let has_scores = self
+            .vulnerabilities
+            .iter()
+            .flatten()
+            .any(|vulnerability| vulnerability.scores.is_some());
+        if !has_scores {
+            errors.push(ValidationError {
+                test: "4.5",
+                path: "/vulnerabilities".to_string(),
+                message: "security advisory profile requires at least one vulnerability score".to_string(),
+            });
+        }
+    }
+
+    /// Every `ProductIdT` referenced from `vulnerabilities`, alongside the vulnerability's index
+    /// and a JSON pointer to where it was found.
+    fn referenced_product_ids(&self) -> Vec<(usize, &ProductIdT, String)> {
+        let mut out = Vec::new();
+        for (v_idx, vulnerability) in self.vulnerabilities.iter().flatten().enumerate() {
+            if let Some(status) = &vulnerability.product_status {
+                let groups: [(&str, &Option<Vec<ProductIdT>>); 8] = [
+                    ("first_affected", &status.first_affected),
+                    ("first_fixed", &status.first_fixed),
+                    ("fixed", &status.fixed),
+                    ("known_affected", &status.known_affected),
+                    ("known_not_affected", &status.known_not_affected),
+                    ("last_affected", &status.last_affected),
+                    ("recommended", &status.recommended),
+                    ("under_investigation", &status.under_investigation),
+                ];
+                for (field, ids) in groups {
+                    for (id_idx, product_id) in ids.iter().flatten().enumerate() {
+                        out.push((
+                            v_idx,
+                            product_id,
+                            format!("/vulnerabilities/{v_idx}/product_status/{field}/{id_idx}"),
+                        ));
+                    }
+                }
+            }
+            for (r_idx, remediation) in vulnerability.remediations.iter().flatten().enumerate() {
+                for (id_idx, product_id) in remediation.product_ids.iter().flatten().enumerate() {
+                    out.push((
+                        v_idx,
+                        product_id,
+                        format!("/vulnerabilities/{v_idx}/remediations/{r_idx}/product_ids/{id_idx}"),
+                    ));
+                }
+            }
+            for (s_idx, score) in vulnerability.scores.iter().flatten().enumerate() {
+                for (id_idx, product_id) in score.products.iter().enumerate() {
+                    out.push((
+                        v_idx,
+                        product_id,
+                        format!("/vulnerabilities/{v_idx}/scores/{s_idx}/products/{id_idx}"),
+                    ));
+                }
+            }
+            for (t_idx, threat) in vulnerability.threats.iter().flatten().enumerate() {
+                for (id_idx, product_id) in threat.product_ids.iter().flatten().enumerate() {
+                    out.push((
+                        v_idx,
+                        product_id,
+                        format!("/vulnerabilities/{v_idx}/threats/{t_idx}/product_ids/{id_idx}"),
+                    ));
+                }
+            }
+            for (f_idx, flag) in vulnerability.flags.iter().flatten().enumerate() {
+                for (id_idx, product_id) in flag.product_ids.iter().flatten().enumerate() {
+                    out.push((
+                        v_idx,
+                        product_id,
+                        format!("/vulnerabilities/{v_idx}/flags/{f_idx}/product_ids/{id_idx}"),
+                    ));
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{
+        CsafVersion, Document, Publisher, PublisherCategory, Revision, Tracking, TrackingStatus,
+    };
+    use crate::vulnerability::{ProductStatus, Vulnerability};
+
+    fn document_with_revisions(numbers: &[&str], tracking_version: &str) -> Document {
+        Document {
+            acknowledgments: None,
+            aggregate_severity: None,
+            category: "generic_csaf".to_string(),
+            csaf_version: CsafVersion::V2_0,
+            distribution: None,
+            lang: None,
+            notes: None,
+            publisher: Publisher {
+                category: PublisherCategory::Vendor,
+                contact_details: None,
+                issuing_authority: None,
+                name: "Test".to_string(),
+                namespace: "https://example.com".to_string(),
+            },
+            references: None,
+            source_lang: None,
+            title: "Test".to_string(),
+            tracking: Tracking {
+                aliases: None,
+                current_release_date: "2024-01-01T00:00:00Z".to_string(),
+                generator: None,
+                id: "TEST-1".to_string(),
+                initial_release_date: "2024-01-01T00:00:00Z".to_string(),
+                revision_history: numbers
+                    .iter()
+                    .map(|number| Revision {
+                        date: "2024-01-01T00:00:00Z".to_string(),
+                        legacy_version: None,
+                        number: number.to_string(),
+                        summary: "Revision.".to_string(),
+                    })
+                    .collect(),
+                status: TrackingStatus::Final,
+                version: tracking_version.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn ten_revisions_do_not_trigger_a_spurious_lexicographic_mismatch() {
+        let document = document_with_revisions(&["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"], "10");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: None,
+        };
+        let mut errors = Vec::new();
+        csaf.check_revision_history(&mut errors);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn version_not_matching_latest_revision_is_flagged() {
+        let document = document_with_revisions(&["1", "2"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: None,
+        };
+        let mut errors = Vec::new();
+        csaf.check_revision_history(&mut errors);
+        assert!(errors.iter().any(|error| error.test == "6.1.1.5"));
+    }
+
+    #[test]
+    fn duplicate_revision_number_is_flagged() {
+        let document = document_with_revisions(&["1", "1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: None,
+        };
+        let mut errors = Vec::new();
+        csaf.check_revision_history(&mut errors);
+        assert!(errors.iter().any(|error| error.test == "6.1.1.4"));
+    }
+
+    #[test]
+    fn contradicting_known_affected_and_known_not_affected_is_flagged() {
+        let document = document_with_revisions(&["1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                product_status: Some(ProductStatus {
+                    known_affected: Some(vec!["P1".to_string()]),
+                    known_not_affected: Some(vec!["P1".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]),
+        };
+        let mut errors = Vec::new();
+        csaf.check_contradicting_product_status(&mut errors);
+        assert!(errors.iter().any(|error| error.test == "6.1.1.3"));
+    }
+
+    #[test]
+    fn non_contradicting_status_passes() {
+        let document = document_with_revisions(&["1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                product_status: Some(ProductStatus {
+                    known_affected: Some(vec!["P1".to_string()]),
+                    known_not_affected: Some(vec!["P2".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]),
+        };
+        let mut errors = Vec::new();
+        csaf.check_contradicting_product_status(&mut errors);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn dangling_product_id_in_a_score_is_caught() {
+        let document = document_with_revisions(&["1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                scores: Some(vec![crate::vulnerability::Score {
+                    cvss_v2: None,
+                    cvss_v3: None,
+                    products: vec!["DANGLING".to_string()],
+                }]),
+                ..Default::default()
+            }]),
+        };
+        let mut errors = Vec::new();
+        csaf.check_product_ids_defined(&mut errors);
+        assert!(errors
+            .iter()
+            .any(|error| error.path == "/vulnerabilities/0/scores/0/products/0"));
+    }
+
+    #[test]
+    fn dangling_product_id_in_a_threat_is_caught() {
+        let document = document_with_revisions(&["1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                threats: Some(vec![crate::vulnerability::Threat {
+                    category: crate::vulnerability::ThreatCategory::ExploitStatus,
+                    date: None,
+                    details: "Actively exploited.".to_string(),
+                    group_ids: None,
+                    product_ids: Some(vec!["DANGLING".to_string()]),
+                }]),
+                ..Default::default()
+            }]),
+        };
+        let mut errors = Vec::new();
+        csaf.check_product_ids_defined(&mut errors);
+        assert!(errors
+            .iter()
+            .any(|error| error.path == "/vulnerabilities/0/threats/0/product_ids/0"));
+    }
+
+    #[test]
+    fn dangling_product_id_in_a_flag_is_caught() {
+        let document = document_with_revisions(&["1"], "1");
+        let csaf = Csaf {
+            document,
+            product_tree: None,
+            vulnerabilities: Some(vec![Vulnerability {
+                flags: Some(vec![crate::vulnerability::Flag {
+                    date: None,
+                    group_ids: None,
+                    label: crate::vulnerability::FlagLabel::ComponentNotPresent,
+                    product_ids: Some(vec!["DANGLING".to_string()]),
+                }]),
+                ..Default::default()
+            }]),
+        };
+        let mut errors = Vec::new();
+        csaf.check_product_ids_defined(&mut errors);
+        assert!(errors
+            .iter()
+            .any(|error| error.path == "/vulnerabilities/0/flags/0/product_ids/0"));
+    }
+}
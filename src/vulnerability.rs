@@ -0,0 +1,332 @@
+//! [Vulnerabilities property](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#33-vulnerabilities-property)
+
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{Acknowledgment, NoteT, ProductGroupIdT, ProductIdT, ReferenceT};
+use crate::product_tree::ProductGroup;
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Vulnerability {
+    pub acknowledgments: Option<Vec<Acknowledgment>>,
+    pub cve: Option<String>,
+    pub cwe: Option<Cwe>,
+    pub discovery_date: Option<String>,
+    pub flags: Option<Vec<Flag>>,
+    pub ids: Option<Vec<Id>>,
+    pub involvements: Option<Vec<Involvement>>,
+    pub notes: Option<Vec<NoteT>>,
+    pub product_status: Option<ProductStatus>,
+    pub references: Option<Vec<ReferenceT>>,
+    pub release_date: Option<String>,
+    pub remediations: Option<Vec<Remediation>>,
+    pub scores: Option<Vec<Score>>,
+    pub threats: Option<Vec<Threat>>,
+    pub title: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Cwe {
+    pub id: String,
+    pub name: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Id {
+    pub system_name: String,
+    pub text: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Flag {
+    pub date: Option<String>,
+    pub group_ids: Option<Vec<ProductGroupIdT>>,
+    pub label: FlagLabel,
+    pub product_ids: Option<Vec<ProductIdT>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagLabel {
+    ComponentNotPresent,
+    InlineMitigationsAlreadyExist,
+    VulnerableCodeCannotBeControlledByAdversary,
+    VulnerableCodeNotInExecutePath,
+    VulnerableCodeNotPresent,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Involvement {
+    pub date: Option<String>,
+    pub party: InvolvementParty,
+    pub status: InvolvementStatus,
+    pub summary: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvolvementParty {
+    Coordinator,
+    Discoverer,
+    Other,
+    User,
+    Vendor,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvolvementStatus {
+    Completed,
+    ContactAttempted,
+    Disputed,
+    InProgress,
+    NotContacted,
+    Open,
+}
+
+/// [Product status](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3165-vulnerabilities-property---product-status)
+///
+/// The spec requires that a `product_id` appear in at most one of these groups for a given
+/// vulnerability.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProductStatus {
+    pub first_affected: Option<Vec<ProductIdT>>,
+    pub first_fixed: Option<Vec<ProductIdT>>,
+    pub fixed: Option<Vec<ProductIdT>>,
+    pub known_affected: Option<Vec<ProductIdT>>,
+    pub known_not_affected: Option<Vec<ProductIdT>>,
+    pub last_affected: Option<Vec<ProductIdT>>,
+    pub recommended: Option<Vec<ProductIdT>>,
+    pub under_investigation: Option<Vec<ProductIdT>>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Remediation {
+    pub category: RemediationCategory,
+    pub date: Option<String>,
+    pub details: String,
+    pub entitlements: Option<Vec<String>>,
+    pub group_ids: Option<Vec<ProductGroupIdT>>,
+    pub product_ids: Option<Vec<ProductIdT>>,
+    pub restart_required: Option<RestartRequired>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationCategory {
+    Mitigation,
+    NoFixPlanned,
+    NoneAvailable,
+    VendorFix,
+    Workaround,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RestartRequired {
+    pub category: String,
+    pub details: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    pub cvss_v2: Option<serde_json::Value>,
+    pub cvss_v3: Option<serde_json::Value>,
+    pub products: Vec<ProductIdT>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Threat {
+    pub category: ThreatCategory,
+    pub date: Option<String>,
+    pub details: String,
+    pub group_ids: Option<Vec<ProductGroupIdT>>,
+    pub product_ids: Option<Vec<ProductIdT>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreatCategory {
+    ExploitStatus,
+    Impact,
+    TargetSet,
+}
+
+/// The four [VEX status buckets](https://github.com/oasis-tcs/csaf/blob/master/csaf_2.0/prose/csaf-v2-editor-draft.md#3165-vulnerabilities-property---product-status)
+/// a product can fall into for a given vulnerability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VexStatus {
+    Fixed,
+    KnownAffected,
+    KnownNotAffected,
+    UnderInvestigation,
+}
+
+/// The answer to "is product P affected by this vulnerability, and what is the remediation?",
+/// produced by [`crate::Csaf::status_for_product`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VexFinding<'a> {
+    pub cve: Option<&'a str>,
+    pub ids: &'a [Id],
+    pub status: VexStatus,
+    pub remediations: Vec<&'a Remediation>,
+    pub flags: Vec<&'a Flag>,
+    pub threats: Vec<&'a Threat>,
+}
+
+impl Vulnerability {
+    /// Classify `product_id` into one of the four VEX status buckets for this vulnerability, and
+    /// collect the remediations/flags/threats that apply to it. A remediation/flag/threat applies
+    /// if `product_id` is in its `product_ids`, or in a `product_groups` group named by its
+    /// `group_ids` - `product_groups` should come from the document's `product_tree`. Only the
+    /// `fixed`, `known_not_affected`, `under_investigation` and `known_affected` groups are
+    /// consulted: returns `None` when `product_id` is in none of them, including when it is
+    /// listed only in `first_affected`, `first_fixed`, `last_affected` or `recommended`.
+    pub fn vex_finding(&self, product_id: &ProductIdT, product_groups: &[ProductGroup]) -> Option<VexFinding<'_>> {
+        let status = self.product_status.as_ref()?;
+        let contains = |ids: &Option<Vec<ProductIdT>>| {
+            ids.as_deref().unwrap_or_default().contains(product_id)
+        };
+
+        let vex_status = if contains(&status.fixed) {
+            VexStatus::Fixed
+        } else if contains(&status.known_not_affected) {
+            VexStatus::KnownNotAffected
+        } else if contains(&status.under_investigation) {
+            VexStatus::UnderInvestigation
+        } else if contains(&status.known_affected) {
+            VexStatus::KnownAffected
+        } else {
+            return None;
+        };
+
+        let in_group = |group_ids: &'_ Option<Vec<ProductGroupIdT>>| {
+            group_ids.as_deref().unwrap_or_default().iter().any(|group_id| {
+                product_groups
+                    .iter()
+                    .any(|group| &group.group_id == group_id && group.product_ids.contains(product_id))
+            })
+        };
+        let applies_to = |product_ids: &'_ Option<Vec<ProductIdT>>, group_ids: &'_ Option<Vec<ProductGroupIdT>>| {
+            product_ids.as_deref().unwrap_or_default().contains(product_id) || in_group(group_ids)
+        };
+
+        Some(VexFinding {
+            cve: self.cve.as_deref(),
+            ids: self.ids.as_deref().unwrap_or_default(),
+            status: vex_status,
+            remediations: self
+                .remediations
+                .iter()
+                .flatten()
+                .filter(|r| applies_to(&r.product_ids, &r.group_ids))
+                .collect(),
+            flags: self
+                .flags
+                .iter()
+                .flatten()
+                .filter(|f| applies_to(&f.product_ids, &f.group_ids))
+                .collect(),
+            threats: self
+                .threats
+                .iter()
+                .flatten()
+                .filter(|t| applies_to(&t.product_ids, &t.group_ids))
+                .collect(),
+        })
+    }
+
+    /// All `product_id`s this vulnerability's `product_status` places into any of the four VEX
+    /// buckets, together with the bucket each falls into. Used by [`crate::Csaf::affecting_products`]
+    /// to answer "which products does CVE C affect, and how".
+    pub fn affected_product_ids(&self) -> Vec<(&ProductIdT, VexStatus)> {
+        let Some(status) = &self.product_status else {
+            return Vec::new();
+        };
+        let bucket = |ids: &Option<Vec<ProductIdT>>, vex_status: VexStatus| {
+            ids.iter()
+                .flatten()
+                .map(move |id| (id, vex_status))
+                .collect::<Vec<_>>()
+        };
+        [
+            bucket(&status.fixed, VexStatus::Fixed),
+            bucket(&status.known_not_affected, VexStatus::KnownNotAffected),
+            bucket(&status.under_investigation, VexStatus::UnderInvestigation),
+            bucket(&status.known_affected, VexStatus::KnownAffected),
+        ]
+        .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remediation_targeted_by_group_id_applies_to_a_member_product() {
+        let vulnerability = Vulnerability {
+            product_status: Some(ProductStatus {
+                known_affected: Some(vec!["P1".to_string()]),
+                ..Default::default()
+            }),
+            remediations: Some(vec![Remediation {
+                category: RemediationCategory::VendorFix,
+                date: None,
+                details: "Upgrade.".to_string(),
+                entitlements: None,
+                group_ids: Some(vec!["G1".to_string()]),
+                product_ids: None,
+                restart_required: None,
+                url: None,
+            }]),
+            ..Default::default()
+        };
+        let groups = vec![ProductGroup {
+            group_id: "G1".to_string(),
+            product_ids: vec!["P1".to_string()],
+            summary: None,
+        }];
+
+        let finding = vulnerability.vex_finding(&"P1".to_string(), &groups).unwrap();
+        assert_eq!(finding.remediations.len(), 1);
+    }
+
+    #[test]
+    fn remediation_targeted_by_group_id_does_not_apply_to_a_non_member_product() {
+        let vulnerability = Vulnerability {
+            product_status: Some(ProductStatus {
+                known_affected: Some(vec!["P2".to_string()]),
+                ..Default::default()
+            }),
+            remediations: Some(vec![Remediation {
+                category: RemediationCategory::VendorFix,
+                date: None,
+                details: "Upgrade.".to_string(),
+                entitlements: None,
+                group_ids: Some(vec!["G1".to_string()]),
+                product_ids: None,
+                restart_required: None,
+                url: None,
+            }]),
+            ..Default::default()
+        };
+        let groups = vec![ProductGroup {
+            group_id: "G1".to_string(),
+            product_ids: vec!["P1".to_string()],
+            summary: None,
+        }];
+
+        let finding = vulnerability.vex_finding(&"P2".to_string(), &groups).unwrap();
+        assert!(finding.remediations.is_empty());
+    }
+}